@@ -1,12 +1,25 @@
-use crate::read_exact;
+use crate::{read_exact, read_prefix};
 use byteorder::{BigEndian, ReadBytesExt};
-use std::io::{self, Cursor};
+use ip_network::IpNetwork;
+use std::io::{self, Cursor, Read};
+use std::net::Ipv4Addr;
 
 #[derive(Debug)]
 pub enum Attribute {
     Origin(AttributeOrigin),
     AsPath(AttributeAsPath),
-    Unknown(u8),
+    NextHop(Ipv4Addr),
+    MultiExitDisc(u32),
+    LocalPref(u32),
+    AtomicAggregate,
+    Aggregator(AttributeAggregator),
+    Community(Vec<u32>),
+    As4Path(AttributeAsPath),
+    As4Aggregator(AttributeAggregator),
+    LargeCommunity(Vec<(u32, u32, u32)>),
+    MpReachNlri(AttributeMpReach),
+    MpUnreachNlri(AttributeMpUnreach),
+    Unknown(u8, Vec<u8>),
 }
 
 impl Attribute {
@@ -35,11 +48,198 @@ impl Attribute {
         Ok(match type_id {
             1 => Attribute::Origin(AttributeOrigin::from(data[0])),
             2 => Attribute::AsPath(AttributeAsPath { data }),
-            _ => Attribute::Unknown(type_id),
+            3 => {
+                let mut parts: [u8; 4] = [0; 4];
+                parts.copy_from_slice(&data[..4]);
+                Attribute::NextHop(Ipv4Addr::from(parts))
+            }
+            4 => Attribute::MultiExitDisc(Cursor::new(&data).read_u32::<BigEndian>()?),
+            5 => Attribute::LocalPref(Cursor::new(&data).read_u32::<BigEndian>()?),
+            6 => Attribute::AtomicAggregate,
+            7 => Attribute::Aggregator(AttributeAggregator::parse(&data)?),
+            8 => Attribute::Community(parse_u32_list(&data)?),
+            14 => Attribute::MpReachNlri(AttributeMpReach::parse(&data)?),
+            15 => Attribute::MpUnreachNlri(AttributeMpUnreach::parse(&data)?),
+            17 => Attribute::As4Path(AttributeAsPath { data }),
+            18 => Attribute::As4Aggregator(AttributeAggregator::parse(&data)?),
+            32 => Attribute::LargeCommunity(parse_large_community_list(&data)?),
+            _ => Attribute::Unknown(type_id, data),
         })
     }
 }
 
+/// Borrowed counterpart of [`Attribute`]: holds `&'a [u8]` slices into the caller's
+/// input buffer instead of copying AS_PATH/AS4_PATH/unknown attribute payloads into
+/// owned `Vec<u8>`s. Used by [`crate::zerocopy::SliceParser`] for the zero-copy path
+/// over mmap'd files; the `Read`-based streaming API keeps using owned [`Attribute`].
+#[derive(Debug)]
+pub enum AttributeRef<'a> {
+    Origin(AttributeOrigin),
+    AsPath(AttributeAsPathRef<'a>),
+    NextHop(Ipv4Addr),
+    MultiExitDisc(u32),
+    LocalPref(u32),
+    AtomicAggregate,
+    Aggregator(AttributeAggregator),
+    Community(Vec<u32>),
+    As4Path(AttributeAsPathRef<'a>),
+    As4Aggregator(AttributeAggregator),
+    LargeCommunity(Vec<(u32, u32, u32)>),
+    MpReachNlri(AttributeMpReach),
+    MpUnreachNlri(AttributeMpUnreach),
+    Unknown(u8, &'a [u8]),
+}
+
+impl<'a> AttributeRef<'a> {
+    pub fn parse_all(input: &'a [u8]) -> io::Result<Vec<Self>> {
+        let mut position = 0;
+        let mut output = vec![];
+        while position < input.len() {
+            let (attribute, consumed) = Self::parse(&input[position..])?;
+            output.push(attribute);
+            position += consumed;
+        }
+        Ok(output)
+    }
+
+    fn parse(input: &'a [u8]) -> io::Result<(Self, usize)> {
+        let mut cursor = Cursor::new(input);
+        let flags = cursor.read_u8()?;
+        let has_extra_length = (flags >> 4) & 0x1 == 1;
+        let type_id = cursor.read_u8()?;
+
+        let length = if has_extra_length {
+            cursor.read_u16::<BigEndian>()?
+        } else {
+            cursor.read_u8()? as u16
+        };
+
+        let header_len = cursor.position() as usize;
+        let data = &input[header_len..header_len + length as usize];
+
+        let attribute = match type_id {
+            1 => AttributeRef::Origin(AttributeOrigin::from(data[0])),
+            2 => AttributeRef::AsPath(AttributeAsPathRef { data }),
+            3 => {
+                let mut parts: [u8; 4] = [0; 4];
+                parts.copy_from_slice(&data[..4]);
+                AttributeRef::NextHop(Ipv4Addr::from(parts))
+            }
+            4 => AttributeRef::MultiExitDisc(Cursor::new(data).read_u32::<BigEndian>()?),
+            5 => AttributeRef::LocalPref(Cursor::new(data).read_u32::<BigEndian>()?),
+            6 => AttributeRef::AtomicAggregate,
+            7 => AttributeRef::Aggregator(AttributeAggregator::parse(data)?),
+            8 => AttributeRef::Community(parse_u32_list(data)?),
+            14 => AttributeRef::MpReachNlri(AttributeMpReach::parse(data)?),
+            15 => AttributeRef::MpUnreachNlri(AttributeMpUnreach::parse(data)?),
+            17 => AttributeRef::As4Path(AttributeAsPathRef { data }),
+            18 => AttributeRef::As4Aggregator(AttributeAggregator::parse(data)?),
+            32 => AttributeRef::LargeCommunity(parse_large_community_list(data)?),
+            _ => AttributeRef::Unknown(type_id, data),
+        };
+
+        Ok((attribute, header_len + length as usize))
+    }
+}
+
+fn parse_u32_list(data: &[u8]) -> io::Result<Vec<u32>> {
+    let mut cursor = Cursor::new(data);
+    let mut output = vec![];
+    while cursor.position() < data.len() as u64 {
+        output.push(cursor.read_u32::<BigEndian>()?);
+    }
+    Ok(output)
+}
+
+fn parse_large_community_list(data: &[u8]) -> io::Result<Vec<(u32, u32, u32)>> {
+    let mut cursor = Cursor::new(data);
+    let mut output = vec![];
+    while cursor.position() < data.len() as u64 {
+        let global_admin = cursor.read_u32::<BigEndian>()?;
+        let local_data1 = cursor.read_u32::<BigEndian>()?;
+        let local_data2 = cursor.read_u32::<BigEndian>()?;
+        output.push((global_admin, local_data1, local_data2));
+    }
+    Ok(output)
+}
+
+/// AGGREGATOR/AS4_AGGREGATOR: the originating AS (2 or 4 bytes, depending on
+/// whether the speaker negotiated 4-byte ASNs) followed by its 4-byte IP address.
+#[derive(Debug)]
+pub struct AttributeAggregator {
+    pub asn: u32,
+    pub ip: Ipv4Addr,
+}
+
+impl AttributeAggregator {
+    fn parse(data: &[u8]) -> io::Result<Self> {
+        let mut cursor = Cursor::new(data);
+        let asn = if data.len() >= 8 {
+            cursor.read_u32::<BigEndian>()?
+        } else {
+            cursor.read_u16::<BigEndian>()? as u32
+        };
+        let ip = Ipv4Addr::from(cursor.read_u32::<BigEndian>()?);
+
+        Ok(Self { asn, ip })
+    }
+}
+
+#[derive(Debug)]
+pub struct AttributeMpReach {
+    pub afi: u16,
+    pub safi: u8,
+    pub next_hop: Vec<u8>,
+    pub nlri: Vec<IpNetwork>,
+}
+
+impl AttributeMpReach {
+    fn parse(data: &[u8]) -> io::Result<Self> {
+        let mut cursor = Cursor::new(data);
+        let afi = cursor.read_u16::<BigEndian>()?;
+        let safi = cursor.read_u8()?;
+        let next_hop_length = cursor.read_u8()?;
+        let next_hop = read_exact(&mut cursor, next_hop_length as usize)?;
+        let _reserved = cursor.read_u8()?;
+
+        let is_ipv6 = afi == 2;
+        let mut nlri = vec![];
+        while cursor.position() < data.len() as u64 {
+            nlri.push(read_prefix(&mut cursor, is_ipv6)?);
+        }
+
+        Ok(Self {
+            afi,
+            safi,
+            next_hop,
+            nlri,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct AttributeMpUnreach {
+    pub afi: u16,
+    pub safi: u8,
+    pub nlri: Vec<IpNetwork>,
+}
+
+impl AttributeMpUnreach {
+    fn parse(data: &[u8]) -> io::Result<Self> {
+        let mut cursor = Cursor::new(data);
+        let afi = cursor.read_u16::<BigEndian>()?;
+        let safi = cursor.read_u8()?;
+
+        let is_ipv6 = afi == 2;
+        let mut nlri = vec![];
+        while cursor.position() < data.len() as u64 {
+            nlri.push(read_prefix(&mut cursor, is_ipv6)?);
+        }
+
+        Ok(Self { afi, safi, nlri })
+    }
+}
+
 #[derive(Debug)]
 pub enum AttributeOrigin {
     Igp,
@@ -64,6 +264,24 @@ pub struct AttributeAsPath {
     data: Vec<u8>,
 }
 
+/// Borrowed counterpart of [`AttributeAsPath`]: a `&'a [u8]` slice into the caller's
+/// input buffer rather than an owned copy.
+#[derive(Debug)]
+pub struct AttributeAsPathRef<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> AttributeAsPathRef<'a> {
+    pub fn get_path_segments(&self, is_asn_32bit: bool) -> io::Result<Vec<PathSegment>> {
+        let mut cursor = Cursor::new(self.data);
+        let mut output = vec![];
+        while cursor.position() < self.data.len() as u64 {
+            output.push(PathSegment::parse(&mut cursor, is_asn_32bit)?);
+        }
+        Ok(output)
+    }
+}
+
 impl AttributeAsPath {
     pub fn get_path_segments(&self, is_asn_32bit: bool) -> io::Result<Vec<PathSegment>> {
         let mut cursor = Cursor::new(&self.data);
@@ -75,13 +293,13 @@ impl AttributeAsPath {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct PathSegment {
     pub typ: PathSegmentType,
     pub values: Vec<u32>,
 }
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum PathSegmentType {
     AsSet,
     AsSequence,
@@ -114,3 +332,122 @@ impl PathSegment {
         Ok(Self { typ, values })
     }
 }
+
+/// A full BGP message as carried inside a BGP4MP MESSAGE record: a 16-byte marker,
+/// a 2-byte total length, a 1-byte type, and a type-specific body.
+#[derive(Debug)]
+pub enum BgpMessage {
+    Open(BgpOpen),
+    Update(BgpUpdate),
+    Notification(BgpNotification),
+    KeepAlive,
+    Unknown(u8, Vec<u8>),
+}
+
+impl BgpMessage {
+    pub fn parse<R: ReadBytesExt>(reader: &mut R) -> io::Result<Self> {
+        let _marker = read_exact(reader, 16)?;
+        let length = reader.read_u16::<BigEndian>()?;
+        let typ = reader.read_u8()?;
+        let body = read_exact(reader, (length as usize).saturating_sub(19))?;
+        let mut cursor = Cursor::new(body.as_slice());
+
+        Ok(match typ {
+            1 => BgpMessage::Open(BgpOpen::parse(&mut cursor)?),
+            2 => BgpMessage::Update(BgpUpdate::parse(&mut cursor, body.len())?),
+            3 => BgpMessage::Notification(BgpNotification::parse(&mut cursor)?),
+            4 => BgpMessage::KeepAlive,
+            n => BgpMessage::Unknown(n, body),
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct BgpOpen {
+    pub version: u8,
+    pub my_as: u16,
+    pub hold_time: u16,
+    pub bgp_identifier: Ipv4Addr,
+    pub opt_parameters: Vec<u8>,
+}
+
+impl BgpOpen {
+    fn parse<R: ReadBytesExt>(rdr: &mut R) -> io::Result<Self> {
+        let version = rdr.read_u8()?;
+        let my_as = rdr.read_u16::<BigEndian>()?;
+        let hold_time = rdr.read_u16::<BigEndian>()?;
+        let bgp_identifier = Ipv4Addr::from(rdr.read_u32::<BigEndian>()?);
+        let opt_parameters_length = rdr.read_u8()?;
+        let opt_parameters = read_exact(rdr, opt_parameters_length as usize)?;
+
+        Ok(Self {
+            version,
+            my_as,
+            hold_time,
+            bgp_identifier,
+            opt_parameters,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct BgpUpdate {
+    pub withdrawn_routes: Vec<IpNetwork>,
+    pub path_attributes: Vec<Attribute>,
+    pub nlri: Vec<IpNetwork>,
+}
+
+impl BgpUpdate {
+    fn parse<R: ReadBytesExt>(rdr: &mut R, body_length: usize) -> io::Result<Self> {
+        let withdrawn_routes_length = rdr.read_u16::<BigEndian>()? as usize;
+        let withdrawn_routes = Self::parse_prefixes(&read_exact(rdr, withdrawn_routes_length)?)?;
+
+        let total_path_attribute_length = rdr.read_u16::<BigEndian>()? as usize;
+        let path_attributes =
+            Attribute::parse_all(&read_exact(rdr, total_path_attribute_length)?)?;
+
+        let nlri_length = body_length
+            - 2
+            - withdrawn_routes_length
+            - 2
+            - total_path_attribute_length;
+        let nlri = Self::parse_prefixes(&read_exact(rdr, nlri_length)?)?;
+
+        Ok(Self {
+            withdrawn_routes,
+            path_attributes,
+            nlri,
+        })
+    }
+
+    fn parse_prefixes(input: &[u8]) -> io::Result<Vec<IpNetwork>> {
+        let mut cursor = Cursor::new(input);
+        let mut output = vec![];
+        while cursor.position() < input.len() as u64 {
+            output.push(read_prefix(&mut cursor, false)?);
+        }
+        Ok(output)
+    }
+}
+
+#[derive(Debug)]
+pub struct BgpNotification {
+    pub error_code: u8,
+    pub error_subcode: u8,
+    pub data: Vec<u8>,
+}
+
+impl BgpNotification {
+    fn parse<R: ReadBytesExt>(rdr: &mut R) -> io::Result<Self> {
+        let error_code = rdr.read_u8()?;
+        let error_subcode = rdr.read_u8()?;
+        let mut data = vec![];
+        rdr.read_to_end(&mut data)?;
+
+        Ok(Self {
+            error_code,
+            error_subcode,
+            data,
+        })
+    }
+}
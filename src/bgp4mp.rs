@@ -0,0 +1,150 @@
+use crate::bgp::BgpMessage;
+use crate::{read_ip_addr, Bgp4mpSubtype, Message, MrtHeader, MrtType};
+use byteorder::{BigEndian, ReadBytesExt};
+use std::io;
+use std::net::IpAddr;
+
+fn is_as4(subtype: Bgp4mpSubtype) -> bool {
+    matches!(
+        subtype,
+        Bgp4mpSubtype::MessageAs4
+            | Bgp4mpSubtype::StateChangeAs4
+            | Bgp4mpSubtype::MessageAs4Local
+    )
+}
+
+fn subtype_and_microsecond_timestamp<R: ReadBytesExt>(
+    reader: &mut R,
+    header: &MrtHeader,
+) -> io::Result<(Bgp4mpSubtype, Option<u32>)> {
+    match header.typ {
+        MrtType::Bgp4mp(subtype) => Ok((subtype, None)),
+        MrtType::Bgp4mpEt(subtype) => {
+            let microsecond_timestamp = reader.read_u32::<BigEndian>()?;
+            Ok((subtype, Some(microsecond_timestamp)))
+        }
+        _ => panic!("Only Bgp4mp and Bgp4mpEt types are supported"),
+    }
+}
+
+fn read_asn<R: ReadBytesExt>(rdr: &mut R, is_asn_32bit: bool) -> io::Result<u32> {
+    if is_asn_32bit {
+        rdr.read_u32::<BigEndian>()
+    } else {
+        Ok(rdr.read_u16::<BigEndian>()? as u32)
+    }
+}
+
+#[derive(Debug)]
+pub struct Bgp4mpStateChange {
+    pub microsecond_timestamp: Option<u32>,
+    pub peer_as: u32,
+    pub local_as: u32,
+    pub interface_index: u16,
+    pub afi: u16,
+    pub peer_ip: IpAddr,
+    pub local_ip: IpAddr,
+    pub old_state: u16,
+    pub new_state: u16,
+}
+
+impl Message<Bgp4mpStateChange> for Bgp4mpStateChange {
+    fn parse<R: ReadBytesExt>(reader: &mut R, header: &MrtHeader) -> io::Result<Self> {
+        let (subtype, microsecond_timestamp) =
+            subtype_and_microsecond_timestamp(reader, header)?;
+        let is_as4 = is_as4(subtype);
+
+        let peer_as = read_asn(reader, is_as4)?;
+        let local_as = read_asn(reader, is_as4)?;
+        let interface_index = reader.read_u16::<BigEndian>()?;
+        let afi = reader.read_u16::<BigEndian>()?;
+        let is_ipv6 = afi == 2;
+        let peer_ip = read_ip_addr(reader, is_ipv6)?;
+        let local_ip = read_ip_addr(reader, is_ipv6)?;
+        let old_state = reader.read_u16::<BigEndian>()?;
+        let new_state = reader.read_u16::<BigEndian>()?;
+
+        Ok(Self {
+            microsecond_timestamp,
+            peer_as,
+            local_as,
+            interface_index,
+            afi,
+            peer_ip,
+            local_ip,
+            old_state,
+            new_state,
+        })
+    }
+
+    fn can_parse(typ: MrtType) -> bool {
+        matches!(
+            typ,
+            MrtType::Bgp4mp(Bgp4mpSubtype::StateChange)
+                | MrtType::Bgp4mp(Bgp4mpSubtype::StateChangeAs4)
+                | MrtType::Bgp4mpEt(Bgp4mpSubtype::StateChange)
+                | MrtType::Bgp4mpEt(Bgp4mpSubtype::StateChangeAs4)
+        )
+    }
+}
+
+#[derive(Debug)]
+pub struct Bgp4mpMessage {
+    pub microsecond_timestamp: Option<u32>,
+    pub peer_as: u32,
+    pub local_as: u32,
+    pub interface_index: u16,
+    pub afi: u16,
+    pub peer_ip: IpAddr,
+    pub local_ip: IpAddr,
+    pub message: BgpMessage,
+}
+
+impl Message<Bgp4mpMessage> for Bgp4mpMessage {
+    fn parse<R: ReadBytesExt>(reader: &mut R, header: &MrtHeader) -> io::Result<Self> {
+        let (subtype, microsecond_timestamp) =
+            subtype_and_microsecond_timestamp(reader, header)?;
+        let is_as4 = is_as4(subtype);
+
+        let peer_as = read_asn(reader, is_as4)?;
+        let local_as = read_asn(reader, is_as4)?;
+        let interface_index = reader.read_u16::<BigEndian>()?;
+        let afi = reader.read_u16::<BigEndian>()?;
+        let is_ipv6 = afi == 2;
+        let peer_ip = read_ip_addr(reader, is_ipv6)?;
+        let local_ip = read_ip_addr(reader, is_ipv6)?;
+        let message = BgpMessage::parse(reader)?;
+
+        Ok(Self {
+            microsecond_timestamp,
+            peer_as,
+            local_as,
+            interface_index,
+            afi,
+            peer_ip,
+            local_ip,
+            message,
+        })
+    }
+
+    fn can_parse(typ: MrtType) -> bool {
+        matches!(
+            typ,
+            MrtType::Bgp4mp(Bgp4mpSubtype::Message)
+                | MrtType::Bgp4mp(Bgp4mpSubtype::MessageAs4)
+                | MrtType::Bgp4mp(Bgp4mpSubtype::MessageLocal)
+                | MrtType::Bgp4mp(Bgp4mpSubtype::MessageAs4Local)
+                | MrtType::Bgp4mpEt(Bgp4mpSubtype::Message)
+                | MrtType::Bgp4mpEt(Bgp4mpSubtype::MessageAs4)
+                | MrtType::Bgp4mpEt(Bgp4mpSubtype::MessageLocal)
+                | MrtType::Bgp4mpEt(Bgp4mpSubtype::MessageAs4Local)
+        )
+    }
+}
+
+/// Either shape a BGP4MP(_ET) record can take, as yielded by [`crate::Records`].
+#[derive(Debug)]
+pub enum Bgp4mpRecord {
+    StateChange(Bgp4mpStateChange),
+    Message(Bgp4mpMessage),
+}
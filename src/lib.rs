@@ -5,7 +5,10 @@ use byteorder::{BigEndian, ReadBytesExt};
 use ip_network::{IpNetwork, Ipv4Network, Ipv6Network};
 
 pub mod bgp;
+pub mod bgp4mp;
 pub mod processor;
+pub mod routing_table;
+pub mod zerocopy;
 
 pub trait Message<M> {
     fn parse<R: ReadBytesExt>(reader: &mut R, header: &MrtHeader) -> io::Result<M>;
@@ -27,10 +30,23 @@ pub enum TableDumpV2 {
     Unknown(u16),
 }
 
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Bgp4mpSubtype {
+    StateChange,
+    Message,
+    MessageAs4,
+    StateChangeAs4,
+    MessageLocal,
+    MessageAs4Local,
+    Unknown(u16),
+}
+
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum MrtType {
     TableDump(TableDump),
     TableDumpV2(TableDumpV2),
+    Bgp4mp(Bgp4mpSubtype),
+    Bgp4mpEt(Bgp4mpSubtype),
     Unknown(u16),
 }
 
@@ -56,24 +72,9 @@ impl<R: ReadBytesExt> Parser<R> {
         let subtype = self.reader.read_u16::<BigEndian>()?;
         let length = self.reader.read_u32::<BigEndian>()?;
 
-        let typ = match typ {
-            12 => MrtType::TableDump(match subtype {
-                1 => TableDump::AfiIpv4,
-                2 => TableDump::AfiIpv6,
-                _ => TableDump::Unknown(subtype),
-            }),
-            13 => MrtType::TableDumpV2(match subtype {
-                1 => TableDumpV2::PeerIndex,
-                2 => TableDumpV2::RibIpv4Unicast,
-                4 => TableDumpV2::RibIpv6Unicast,
-                _ => TableDumpV2::Unknown(subtype),
-            }),
-            _ => MrtType::Unknown(typ),
-        };
-
         Ok(MrtHeader {
             timestamp,
-            typ,
+            typ: parse_mrt_type(typ, subtype),
             length,
         })
     }
@@ -90,6 +91,76 @@ impl<R: ReadBytesExt> Parser<R> {
 
         M::parse(&mut self.reader, header)
     }
+
+    /// Iterates over every record in the stream, dispatching each to the matching
+    /// [`Message`] impl based on its header and bounding the read to `header.length`
+    /// bytes. Unlike [`Parser::read_message`], this never panics on an unexpected type:
+    /// records it doesn't recognize come back as `Record::Unknown`, and a malformed
+    /// record only poisons itself (via `Some(Err(_))`) rather than the rest of the
+    /// stream, since the bounded read has already put the cursor at the next header.
+    pub fn records(&mut self) -> Records<R> {
+        Records { parser: self }
+    }
+}
+
+pub struct Records<'p, R: ReadBytesExt> {
+    parser: &'p mut Parser<R>,
+}
+
+#[derive(Debug)]
+pub enum Record {
+    PeerIndex(PeerIndexTable),
+    Rib(RibEntry),
+    TableDump(Afi),
+    Bgp4mp(bgp4mp::Bgp4mpRecord),
+    Unknown { header: MrtHeader, raw: Vec<u8> },
+}
+
+impl<'p, R: ReadBytesExt> Iterator for Records<'p, R> {
+    type Item = io::Result<Record>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let header = match self.parser.read_header() {
+            Ok(header) => header,
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return None,
+            Err(e) => return Some(Err(e)),
+        };
+
+        let raw = match read_exact(&mut self.parser.reader, header.length as usize) {
+            Ok(raw) => raw,
+            Err(e) => return Some(Err(e)),
+        };
+
+        Some(parse_record(header, raw))
+    }
+}
+
+fn parse_record(header: MrtHeader, raw: Vec<u8>) -> io::Result<Record> {
+    match header.typ {
+        MrtType::TableDumpV2(TableDumpV2::PeerIndex) => {
+            PeerIndexTable::parse(&mut io::Cursor::new(raw.as_slice()), &header)
+                .map(Record::PeerIndex)
+        }
+        MrtType::TableDumpV2(TableDumpV2::RibIpv4Unicast)
+        | MrtType::TableDumpV2(TableDumpV2::RibIpv6Unicast) => {
+            RibEntry::parse(&mut io::Cursor::new(raw.as_slice()), &header).map(Record::Rib)
+        }
+        MrtType::TableDump(TableDump::AfiIpv4) | MrtType::TableDump(TableDump::AfiIpv6) => {
+            Afi::parse(&mut io::Cursor::new(raw.as_slice()), &header).map(Record::TableDump)
+        }
+        MrtType::Bgp4mp(Bgp4mpSubtype::StateChange)
+        | MrtType::Bgp4mp(Bgp4mpSubtype::StateChangeAs4)
+        | MrtType::Bgp4mpEt(Bgp4mpSubtype::StateChange)
+        | MrtType::Bgp4mpEt(Bgp4mpSubtype::StateChangeAs4) => {
+            bgp4mp::Bgp4mpStateChange::parse(&mut io::Cursor::new(raw.as_slice()), &header)
+                .map(|record| Record::Bgp4mp(bgp4mp::Bgp4mpRecord::StateChange(record)))
+        }
+        typ if bgp4mp::Bgp4mpMessage::can_parse(typ) => {
+            bgp4mp::Bgp4mpMessage::parse(&mut io::Cursor::new(raw.as_slice()), &header)
+                .map(|record| Record::Bgp4mp(bgp4mp::Bgp4mpRecord::Message(record)))
+        }
+        _ => Ok(Record::Unknown { header, raw }),
+    }
 }
 
 #[derive(Debug)]
@@ -232,30 +303,15 @@ impl Message<RibEntry> for RibEntry {
     fn parse<R: ReadBytesExt>(reader: &mut R, header: &MrtHeader) -> io::Result<Self> {
         let sequence_number = reader.read_u32::<BigEndian>()?;
 
-        let prefix_length = reader.read_u8()?;
-        let prefix_bytes = ((prefix_length + 7) / 8) as usize;
-        let prefix_buffer = read_exact(reader, prefix_bytes)?;
-
-        let prefix = match header.typ {
+        let is_ipv6 = match header.typ {
             MrtType::TableDumpV2(subtype) => match subtype {
-                TableDumpV2::RibIpv4Unicast => {
-                    debug_assert!(prefix_length <= 32);
-                    let mut parts: [u8; 4] = [0; 4];
-                    parts[..prefix_bytes].copy_from_slice(prefix_buffer.as_slice());
-                    let ip = Ipv4Addr::from(parts);
-                    IpNetwork::V4(Ipv4Network::from(ip, prefix_length).unwrap())
-                }
-                TableDumpV2::RibIpv6Unicast => {
-                    debug_assert!(prefix_length <= 128);
-                    let mut parts: [u8; 16] = [0; 16];
-                    parts[..prefix_bytes].copy_from_slice(prefix_buffer.as_slice());
-                    let ip = Ipv6Addr::from(parts);
-                    IpNetwork::V6(Ipv6Network::from(ip, prefix_length).unwrap())
-                }
+                TableDumpV2::RibIpv4Unicast => false,
+                TableDumpV2::RibIpv6Unicast => true,
                 _ => panic!("This parser cannot parse TableDumpV2 {:?} subtype", subtype),
             },
             _ => panic!("This parser cannot parse {:?} type", header.typ),
         };
+        let prefix = read_prefix(reader, is_ipv6)?;
 
         let entry_count = reader.read_u16::<BigEndian>()?;
         let mut sub_entries = Vec::with_capacity(entry_count as usize);
@@ -302,6 +358,41 @@ impl RibSubEntry {
     }
 }
 
+fn parse_mrt_type(typ: u16, subtype: u16) -> MrtType {
+    match typ {
+        12 => MrtType::TableDump(match subtype {
+            1 => TableDump::AfiIpv4,
+            2 => TableDump::AfiIpv6,
+            _ => TableDump::Unknown(subtype),
+        }),
+        13 => MrtType::TableDumpV2(match subtype {
+            1 => TableDumpV2::PeerIndex,
+            2 => TableDumpV2::RibIpv4Unicast,
+            4 => TableDumpV2::RibIpv6Unicast,
+            _ => TableDumpV2::Unknown(subtype),
+        }),
+        16 => MrtType::Bgp4mp(match subtype {
+            0 => Bgp4mpSubtype::StateChange,
+            1 => Bgp4mpSubtype::Message,
+            4 => Bgp4mpSubtype::MessageAs4,
+            5 => Bgp4mpSubtype::StateChangeAs4,
+            6 => Bgp4mpSubtype::MessageLocal,
+            7 => Bgp4mpSubtype::MessageAs4Local,
+            _ => Bgp4mpSubtype::Unknown(subtype),
+        }),
+        17 => MrtType::Bgp4mpEt(match subtype {
+            0 => Bgp4mpSubtype::StateChange,
+            1 => Bgp4mpSubtype::Message,
+            4 => Bgp4mpSubtype::MessageAs4,
+            5 => Bgp4mpSubtype::StateChangeAs4,
+            6 => Bgp4mpSubtype::MessageLocal,
+            7 => Bgp4mpSubtype::MessageAs4Local,
+            _ => Bgp4mpSubtype::Unknown(subtype),
+        }),
+        _ => MrtType::Unknown(typ),
+    }
+}
+
 fn read_ip_addr<R: ReadBytesExt>(rdr: &mut R, is_ipv6: bool) -> io::Result<IpAddr> {
     if is_ipv6 {
         let mut buffer = [0; 16];
@@ -318,3 +409,24 @@ fn read_exact<R: ReadBytesExt>(rdr: &mut R, length: usize) -> io::Result<Vec<u8>
     rdr.read_exact(buffer.as_mut_slice())?;
     Ok(buffer)
 }
+
+/// Reads a length-prefixed NLRI-style prefix: a 1-byte prefix length followed by
+/// `ceil(prefix_length / 8)` address bytes, as used by both TABLE_DUMP_V2 RIB entries
+/// and BGP UPDATE withdrawn routes/NLRI.
+fn read_prefix<R: ReadBytesExt>(reader: &mut R, is_ipv6: bool) -> io::Result<IpNetwork> {
+    let prefix_length = reader.read_u8()?;
+    let prefix_bytes = ((prefix_length + 7) / 8) as usize;
+    let prefix_buffer = read_exact(reader, prefix_bytes)?;
+
+    Ok(if is_ipv6 {
+        debug_assert!(prefix_length <= 128);
+        let mut parts: [u8; 16] = [0; 16];
+        parts[..prefix_bytes].copy_from_slice(prefix_buffer.as_slice());
+        IpNetwork::V6(Ipv6Network::from(Ipv6Addr::from(parts), prefix_length).unwrap())
+    } else {
+        debug_assert!(prefix_length <= 32);
+        let mut parts: [u8; 4] = [0; 4];
+        parts[..prefix_bytes].copy_from_slice(prefix_buffer.as_slice());
+        IpNetwork::V4(Ipv4Network::from(Ipv4Addr::from(parts), prefix_length).unwrap())
+    })
+}
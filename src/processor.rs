@@ -1,4 +1,4 @@
-use crate::bgp::{Attribute, AttributeAsPath, PathSegmentType};
+use crate::bgp::{Attribute, AttributeAsPath, PathSegment, PathSegmentType};
 use crate::{Afi, RibEntry};
 use std::error::Error;
 
@@ -14,15 +14,53 @@ fn is_asn_bogus(input: u32) -> bool {
     input == 0 || (input >= 64_496 && input <= 131_071) || input > 1_000_000
 }
 
-pub fn get_origin_as_from_bgp_attribute_as_path(
-    path: &AttributeAsPath,
-    is_asn_32bit: bool,
-) -> Result<Vec<u32>, Box<dyn Error>> {
-    let path_segments = path.get_path_segments(is_asn_32bit)?;
-    debug_assert!(path_segments[0].typ == PathSegmentType::AsSequence);
+/// Merges AS_PATH with AS4_PATH per RFC 6793: old 2-byte-AS speakers mask ASNs that
+/// don't fit in 16 bits as AS_TRANS (23456) in AS_PATH, and carry the genuine 4-byte
+/// ASNs separately in AS4_PATH.
+///
+/// If AS4_PATH covers at least as many ASNs as AS_PATH, AS_PATH is already complete (or
+/// AS4_PATH is malformed) and is returned unchanged. Otherwise the leading ASNs of
+/// AS_PATH that AS4_PATH doesn't cover are kept and AS4_PATH is appended in full, so
+/// segment boundaries are preserved and the final segment's last element remains the
+/// true origin.
+pub fn reconstructed_path(as_path: &[PathSegment], as4_path: &[PathSegment]) -> Vec<PathSegment> {
+    let as_path_len: usize = as_path.iter().map(|segment| segment.values.len()).sum();
+    let as4_path_len: usize = as4_path.iter().map(|segment| segment.values.len()).sum();
+
+    if as4_path.is_empty() || as4_path_len >= as_path_len {
+        return as_path.to_vec();
+    }
 
+    let mut keep = as_path_len - as4_path_len;
+    let mut output = vec![];
+    for segment in as_path {
+        if keep == 0 {
+            break;
+        }
+        let take = keep.min(segment.values.len());
+        output.push(PathSegment {
+            typ: segment.typ,
+            values: segment.values[..take].to_vec(),
+        });
+        keep -= take;
+    }
+    output.extend(as4_path.iter().cloned());
+    output
+}
+
+/// Determines the origin AS(es) by scanning the path from the end, skipping over
+/// AS_CONFED_SEQUENCE/AS_CONFED_SET segments entirely per BGP confederation rules (they
+/// were added by a member AS on the way out of the confederation and say nothing about
+/// who originated the route). Bogus ASNs (see [`is_asn_bogus`]) are ignored. An AS_SET
+/// gives all of its non-bogus ASNs; an AS_SEQUENCE gives its last non-bogus ASN, and one
+/// holding only bogus ASNs is skipped like a confederation segment. Surfaces an error
+/// when a segment of unknown type is reached, or when no segment yields an origin.
+fn get_origin_as_from_path_segments(
+    path_segments: &[PathSegment],
+) -> Result<Vec<u32>, Box<dyn Error>> {
     for path_segment in path_segments.iter().rev() {
         match path_segment.typ {
+            PathSegmentType::AsConfedSequence | PathSegmentType::AsConfedSet => continue,
             PathSegmentType::AsSequence => {
                 for value in path_segment.values.iter().rev() {
                     if !is_asn_bogus(*value) {
@@ -38,7 +76,7 @@ pub fn get_origin_as_from_bgp_attribute_as_path(
                     .cloned()
                     .collect());
             }
-            _ => {
+            PathSegmentType::Unknown(_) => {
                 return Err(
                     format!("Invalid/Legacy BGP Path Segment: {:?}", path_segment.typ).into(),
                 )
@@ -46,18 +84,53 @@ pub fn get_origin_as_from_bgp_attribute_as_path(
         }
     }
 
-    Err("No origin".into())
+    Err("No non-confederation BGP Path Segment with a non-bogus ASN found".into())
+}
+
+pub fn get_origin_as_from_bgp_attribute_as_path(
+    path: &AttributeAsPath,
+    is_asn_32bit: bool,
+) -> Result<Vec<u32>, Box<dyn Error>> {
+    let path_segments = path.get_path_segments(is_asn_32bit)?;
+    get_origin_as_from_path_segments(&path_segments)
+}
+
+fn get_origin_as_from_attributes(
+    attributes: &[Attribute],
+    is_asn_32bit: bool,
+) -> Result<Option<Vec<u32>>, Box<dyn Error>> {
+    let as_path = attributes.iter().find_map(|attribute| match attribute {
+        Attribute::AsPath(as_path) => Some(as_path),
+        _ => None,
+    });
+    let as_path = match as_path {
+        Some(as_path) => as_path,
+        None => return Ok(None),
+    };
+
+    let as4_path = attributes.iter().find_map(|attribute| match attribute {
+        Attribute::As4Path(as4_path) => Some(as4_path),
+        _ => None,
+    });
+
+    let path_segments = match as4_path {
+        Some(as4_path) => reconstructed_path(
+            &as_path.get_path_segments(is_asn_32bit)?,
+            &as4_path.get_path_segments(true)?,
+        ),
+        None => as_path.get_path_segments(is_asn_32bit)?,
+    };
+
+    Ok(Some(get_origin_as_from_path_segments(&path_segments)?))
 }
 
 pub fn get_origin_as_from_rib_entry(input: &RibEntry) -> Result<Vec<u32>, Box<dyn Error>> {
     let mut output = vec![];
     for sub_entry in &input.sub_entries {
-        for attribute in sub_entry.get_bgp_attributes()? {
-            if let Attribute::AsPath(ref as_path) = attribute {
-                output.append(&mut get_origin_as_from_bgp_attribute_as_path(
-                    as_path, true,
-                )?)
-            }
+        if let Some(mut origin_as) =
+            get_origin_as_from_attributes(&sub_entry.get_bgp_attributes()?, true)?
+        {
+            output.append(&mut origin_as)
         }
     }
 
@@ -67,14 +140,8 @@ pub fn get_origin_as_from_rib_entry(input: &RibEntry) -> Result<Vec<u32>, Box<dy
 }
 
 pub fn get_origin_as_from_afi(afi: &Afi) -> Result<Vec<u32>, Box<dyn Error>> {
-    let mut output = vec![];
-    for attribute in afi.get_bgp_attributes()? {
-        if let Attribute::AsPath(ref as_path) = attribute {
-            output.append(&mut get_origin_as_from_bgp_attribute_as_path(
-                as_path, false,
-            )?)
-        }
-    }
+    let mut output =
+        get_origin_as_from_attributes(&afi.get_bgp_attributes()?, false)?.unwrap_or_default();
 
     output.sort_unstable();
     output.dedup();
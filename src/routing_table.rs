@@ -0,0 +1,154 @@
+use crate::processor::{get_origin_as_from_afi, get_origin_as_from_rib_entry};
+use crate::{Afi, RibEntry};
+use ip_network::IpNetwork;
+use std::collections::HashMap;
+use std::error::Error;
+use std::net::IpAddr;
+use std::rc::Rc;
+
+/// Longest-prefix-match lookup table: ingests parsed RIB entries/AFI records and
+/// answers "which origin AS(es) announce the covering prefix for this IP address?".
+///
+/// IPv4 and IPv6 are kept in separate binary tries keyed by prefix bits, since they
+/// differ in address width. Origin-AS sets are interned so that the common case of
+/// many prefixes sharing the same origin(s) doesn't cost a `Vec<u32>` allocation per
+/// trie node, which matters on full tables (~1M IPv4 prefixes).
+pub struct RoutingTable {
+    ipv4: Trie,
+    ipv6: Trie,
+    interner: AsSetInterner,
+}
+
+impl RoutingTable {
+    pub fn new() -> Self {
+        Self {
+            ipv4: Trie::new(),
+            ipv6: Trie::new(),
+            interner: AsSetInterner::default(),
+        }
+    }
+
+    pub fn insert_rib_entry(&mut self, entry: &RibEntry) -> Result<(), Box<dyn Error>> {
+        let origin_as = get_origin_as_from_rib_entry(entry)?;
+        if !origin_as.is_empty() {
+            self.insert(entry.prefix, origin_as);
+        }
+        Ok(())
+    }
+
+    pub fn insert_afi(&mut self, afi: &Afi) -> Result<(), Box<dyn Error>> {
+        let origin_as = get_origin_as_from_afi(afi)?;
+        if !origin_as.is_empty() {
+            self.insert(afi.prefix, origin_as);
+        }
+        Ok(())
+    }
+
+    fn insert(&mut self, prefix: IpNetwork, origin_as: Vec<u32>) {
+        let origin_as = self.interner.intern(origin_as);
+        match &prefix {
+            IpNetwork::V4(net) => {
+                let bits = net.network_address().octets();
+                let prefix_length = net.netmask();
+                self.ipv4.insert(&bits, prefix_length, prefix, origin_as);
+            }
+            IpNetwork::V6(net) => {
+                let bits = net.network_address().octets();
+                let prefix_length = net.netmask();
+                self.ipv6.insert(&bits, prefix_length, prefix, origin_as);
+            }
+        }
+    }
+
+    pub fn lookup(&self, addr: IpAddr) -> Option<(IpNetwork, Vec<u32>)> {
+        let found = match addr {
+            IpAddr::V4(addr) => self.ipv4.lookup(&addr.octets(), 32),
+            IpAddr::V6(addr) => self.ipv6.lookup(&addr.octets(), 128),
+        };
+
+        found.map(|(prefix, origin_as)| (prefix, origin_as.to_vec()))
+    }
+}
+
+impl Default for RoutingTable {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Interns origin-AS sets so prefixes that share the same origin(s) - the common case,
+/// since one network typically announces many prefixes - share a single allocation
+/// instead of each trie node holding its own `Vec<u32>`.
+#[derive(Default)]
+struct AsSetInterner {
+    sets: HashMap<Rc<[u32]>, ()>,
+}
+
+impl AsSetInterner {
+    fn intern(&mut self, origin_as: Vec<u32>) -> Rc<[u32]> {
+        let origin_as: Rc<[u32]> = origin_as.into();
+        match self.sets.get_key_value(&origin_as) {
+            Some((interned, _)) => interned.clone(),
+            None => {
+                self.sets.insert(origin_as.clone(), ());
+                origin_as
+            }
+        }
+    }
+}
+
+struct TrieNode {
+    entry: Option<(IpNetwork, Rc<[u32]>)>,
+    children: [Option<Box<TrieNode>>; 2],
+}
+
+impl TrieNode {
+    fn empty() -> Self {
+        Self {
+            entry: None,
+            children: [None, None],
+        }
+    }
+}
+
+struct Trie {
+    root: TrieNode,
+}
+
+impl Trie {
+    fn new() -> Self {
+        Self {
+            root: TrieNode::empty(),
+        }
+    }
+
+    fn insert(&mut self, bits: &[u8], prefix_length: u8, prefix: IpNetwork, origin_as: Rc<[u32]>) {
+        let mut node = &mut self.root;
+        for i in 0..prefix_length as usize {
+            node = node.children[bit_at(bits, i) as usize]
+                .get_or_insert_with(|| Box::new(TrieNode::empty()));
+        }
+        node.entry = Some((prefix, origin_as));
+    }
+
+    fn lookup(&self, bits: &[u8], max_bits: u8) -> Option<(IpNetwork, Rc<[u32]>)> {
+        let mut node = &self.root;
+        let mut best = node.entry.clone();
+
+        for i in 0..max_bits as usize {
+            node = match &node.children[bit_at(bits, i) as usize] {
+                Some(child) => child,
+                None => break,
+            };
+            if node.entry.is_some() {
+                best = node.entry.clone();
+            }
+        }
+
+        best
+    }
+}
+
+fn bit_at(bytes: &[u8], index: usize) -> u8 {
+    (bytes[index / 8] >> (7 - (index % 8))) & 1
+}
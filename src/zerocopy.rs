@@ -0,0 +1,192 @@
+use crate::bgp::AttributeRef;
+use crate::{parse_mrt_type, read_prefix, IpNetwork, MrtHeader, MrtType, TableDump, TableDumpV2};
+use std::io::{self, Cursor};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+/// Zero-copy counterpart of [`crate::Parser`]: parses directly out of an in-memory
+/// `&'a [u8]` buffer (e.g. a memory-mapped file) instead of a [`byteorder::ReadBytesExt`]
+/// reader, so [`AfiRef`]/[`RibEntryRef`]/[`AttributeRef`] can hold slices into that
+/// buffer rather than copying their payloads.
+pub struct SliceParser<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> SliceParser<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data, position: 0 }
+    }
+
+    fn take(&mut self, length: usize) -> io::Result<&'a [u8]> {
+        if length > self.data.len() - self.position {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "unexpected end of buffer",
+            ));
+        }
+
+        let slice = &self.data[self.position..self.position + length];
+        self.position += length;
+        Ok(slice)
+    }
+
+    fn read_u8(&mut self) -> io::Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn read_u16(&mut self) -> io::Result<u16> {
+        let bytes = self.take(2)?;
+        Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn read_u32(&mut self) -> io::Result<u32> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn read_ip_addr(&mut self, is_ipv6: bool) -> io::Result<IpAddr> {
+        if is_ipv6 {
+            let bytes = self.take(16)?;
+            let mut buffer = [0; 16];
+            buffer.copy_from_slice(bytes);
+            Ok(IpAddr::V6(Ipv6Addr::from(buffer)))
+        } else {
+            Ok(IpAddr::V4(Ipv4Addr::from(self.read_u32()?)))
+        }
+    }
+
+    pub fn read_header(&mut self) -> io::Result<MrtHeader> {
+        let timestamp = self.read_u32()?;
+        let typ = self.read_u16()?;
+        let subtype = self.read_u16()?;
+        let length = self.read_u32()?;
+
+        Ok(MrtHeader {
+            timestamp,
+            typ: parse_mrt_type(typ, subtype),
+            length,
+        })
+    }
+
+    pub fn skip_message(&mut self, header: &MrtHeader) -> io::Result<()> {
+        self.take(header.length as usize)?;
+        Ok(())
+    }
+
+    pub fn read_afi(&mut self, header: &MrtHeader) -> io::Result<AfiRef<'a>> {
+        let is_ipv6 = match header.typ {
+            MrtType::TableDump(subtype) => match subtype {
+                TableDump::AfiIpv4 => false,
+                TableDump::AfiIpv6 => true,
+                _ => panic!("Only AFI_IPv4 and AFI_IPv6 subtypes are supported"),
+            },
+            _ => panic!("Only TableDump types is supported"),
+        };
+
+        let view_number = self.read_u16()?;
+        let sequence_number = self.read_u16()?;
+        let prefix_ip = self.read_ip_addr(is_ipv6)?;
+        let prefix_length = self.read_u8()?;
+        let prefix = IpNetwork::from(prefix_ip, prefix_length).unwrap();
+        let status = self.read_u8()?;
+        let originated_time = self.read_u32()?;
+        let peer_ip = self.read_ip_addr(is_ipv6)?;
+        let peer_as = self.read_u16()?;
+        let attribute_length = self.read_u16()?;
+        let data = self.take(attribute_length as usize)?;
+
+        Ok(AfiRef {
+            view_number,
+            sequence_number,
+            prefix,
+            status,
+            originated_time,
+            peer_ip,
+            peer_as,
+            data,
+        })
+    }
+
+    pub fn read_rib_entry(&mut self, header: &MrtHeader) -> io::Result<RibEntryRef<'a>> {
+        let sequence_number = self.read_u32()?;
+
+        let is_ipv6 = match header.typ {
+            MrtType::TableDumpV2(subtype) => match subtype {
+                TableDumpV2::RibIpv4Unicast => false,
+                TableDumpV2::RibIpv6Unicast => true,
+                _ => panic!("This parser cannot parse TableDumpV2 {:?} subtype", subtype),
+            },
+            _ => panic!("This parser cannot parse {:?} type", header.typ),
+        };
+        let mut prefix_cursor = Cursor::new(self.remaining());
+        let prefix = read_prefix(&mut prefix_cursor, is_ipv6)?;
+        self.position += prefix_cursor.position() as usize;
+
+        let entry_count = self.read_u16()?;
+        let mut sub_entries = Vec::with_capacity(entry_count as usize);
+        for _ in 0..entry_count {
+            sub_entries.push(self.read_rib_sub_entry()?);
+        }
+
+        Ok(RibEntryRef {
+            sequence_number,
+            prefix,
+            sub_entries,
+        })
+    }
+
+    fn read_rib_sub_entry(&mut self) -> io::Result<RibSubEntryRef<'a>> {
+        let peer_index = self.read_u16()?;
+        let originated_time = self.read_u32()?;
+        let attribute_length = self.read_u16()?;
+        let data = self.take(attribute_length as usize)?;
+
+        Ok(RibSubEntryRef {
+            peer_index,
+            originated_time,
+            data,
+        })
+    }
+
+    fn remaining(&self) -> &'a [u8] {
+        &self.data[self.position..]
+    }
+}
+
+#[derive(Debug)]
+pub struct AfiRef<'a> {
+    pub view_number: u16,
+    pub sequence_number: u16,
+    pub prefix: IpNetwork,
+    pub status: u8,
+    pub originated_time: u32,
+    pub peer_ip: IpAddr,
+    pub peer_as: u16,
+    data: &'a [u8],
+}
+
+impl<'a> AfiRef<'a> {
+    pub fn get_bgp_attributes(&self) -> io::Result<Vec<AttributeRef<'a>>> {
+        AttributeRef::parse_all(self.data)
+    }
+}
+
+#[derive(Debug)]
+pub struct RibEntryRef<'a> {
+    pub sequence_number: u32,
+    pub prefix: IpNetwork,
+    pub sub_entries: Vec<RibSubEntryRef<'a>>,
+}
+
+#[derive(Debug)]
+pub struct RibSubEntryRef<'a> {
+    pub peer_index: u16,
+    pub originated_time: u32,
+    data: &'a [u8],
+}
+
+impl<'a> RibSubEntryRef<'a> {
+    pub fn get_bgp_attributes(&self) -> io::Result<Vec<AttributeRef<'a>>> {
+        AttributeRef::parse_all(self.data)
+    }
+}